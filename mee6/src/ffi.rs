@@ -0,0 +1,36 @@
+//! Optional C FFI layer mirroring `LevelInfo`, enabled by the `ffi` feature.
+//!
+//! This lets bots written in C, C++, Go, or Python (via `cffi`) reuse the mee6 math without
+//! reimplementing the cubic.
+
+use crate::{GrowthRate, LevelInfo, Mee6GrowthRate};
+
+/// Build a new [`LevelInfo`] from `xp` using the mee6 growth rate.
+#[no_mangle]
+pub extern "C" fn level_info_new(xp: u64) -> LevelInfo {
+    LevelInfo::new(xp)
+}
+
+/// Get the level a [`LevelInfo`] represents.
+#[no_mangle]
+pub const extern "C" fn level_info_level(info: LevelInfo) -> u64 {
+    info.level()
+}
+
+/// Get the xp that was input into a [`LevelInfo`].
+#[no_mangle]
+pub const extern "C" fn level_info_xp(info: LevelInfo) -> u64 {
+    info.xp()
+}
+
+/// Get the percentage of the way a [`LevelInfo`] is to gaining a level, from the last level.
+#[no_mangle]
+pub const extern "C" fn level_info_percentage(info: LevelInfo) -> u8 {
+    info.percentage()
+}
+
+/// Get the highest level reachable with `xp` cumulative XP under the mee6 growth rate.
+#[no_mangle]
+pub extern "C" fn level_for_xp(xp: u64) -> u64 {
+    Mee6GrowthRate.level_for_xp(xp)
+}
@@ -10,39 +10,126 @@
 //! A library to calculate mee6 levels.
 //! This can be calculated using the `LevelInfo` struct.
 
+#[cfg(feature = "ffi")]
+mod ffi;
+
+/// A pluggable leveling curve, mapping levels to cumulative XP thresholds and back.
+///
+/// Implement this to model other servers' or games' leveling tables (e.g. Pokémon-style growth
+/// rates) and build a [`LevelInfo`] against it with [`LevelInfo::with_curve`], without forking
+/// the crate.
+pub trait GrowthRate {
+    /// The cumulative XP required to reach `level`.
+    fn xp_for_level(&self, level: u64) -> u64;
+    /// The highest level reachable with `xp` cumulative XP.
+    fn level_for_xp(&self, xp: u64) -> u64;
+}
+
+/// The default mee6 growth rate, following the cubic
+/// `xp = (5 / 6) * level * (2 * level * level + 27 * level + 91)`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+pub struct Mee6GrowthRate;
+
+impl GrowthRate for Mee6GrowthRate {
+    #[inline]
+    fn xp_for_level(&self, level: u64) -> u64 {
+        Self::xp_to_level(level as f64) as u64
+    }
+
+    // The cubic xp = (5/3)L^3 + 22.5L^2 + (455/6)L inverts via Cardano's method: depressing
+    // L = t - 4.5 gives t^3 + pt + q = 0 with p = -15.25, q = -22.5 - 0.6 * xp, whose single
+    // real root is t = cbrt(-q/2 + sqrt(q^2/4 + p^3/27)) + cbrt(-q/2 - sqrt(q^2/4 + p^3/27)).
+    fn level_for_xp(&self, xp: u64) -> u64 {
+        let xp = xp as f64;
+        let p = -15.25;
+        let q = -22.5 - 0.6 * xp;
+        let discriminant = q * q / 4.0 + p * p * p / 27.0;
+        let sqrt_discriminant = libm::sqrt(discriminant);
+        let t = libm::cbrt(-q / 2.0 + sqrt_discriminant) + libm::cbrt(-q / 2.0 - sqrt_discriminant);
+        let mut level = libm::floor(t - 4.5) as u64;
+        // f64 rounding can land one level off near a boundary; nudge back in line with xp_to_level.
+        if xp < Self::xp_to_level(level as f64) {
+            level -= 1;
+        } else if xp >= Self::xp_to_level((level + 1) as f64) {
+            level += 1;
+        }
+        level
+    }
+}
+
+impl Mee6GrowthRate {
+    // mul_add is not no-std
+    #[allow(clippy::suboptimal_flops)]
+    #[inline]
+    fn xp_to_level(level: f64) -> f64 {
+        (5.0 / 6.0) * level * (2.0 * level * level + 27.0 * level + 91.0)
+    }
+}
+
+/// A growth rate backed by a hand-authored table of cumulative XP thresholds.
+///
+/// For servers and games whose leveling isn't defined by a formula. `thresholds[i]` is the XP
+/// required to reach level `i`; the slice must be non-empty and sorted in ascending order.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LookupGrowthRate<'a> {
+    thresholds: &'a [u64],
+}
+
+impl<'a> LookupGrowthRate<'a> {
+    /// Create a new `LookupGrowthRate` over `thresholds`, a `&'static [u64]` (or any other
+    /// borrowed slice) of cumulative XP thresholds sorted in ascending order. `thresholds` must
+    /// be non-empty.
+    #[must_use]
+    pub const fn new(thresholds: &'a [u64]) -> Self {
+        Self { thresholds }
+    }
+}
+
+impl GrowthRate for LookupGrowthRate<'_> {
+    fn xp_for_level(&self, level: u64) -> u64 {
+        let index = (level as usize).min(self.thresholds.len() - 1);
+        self.thresholds[index]
+    }
+
+    fn level_for_xp(&self, xp: u64) -> u64 {
+        (self.thresholds.partition_point(|&threshold| threshold <= xp) as u64).saturating_sub(1)
+    }
+}
+
 /// `LevelInfo` stores all of the data calculated when using `LevelInfo::new`(), so it can be cheaply
 /// gotten with getters.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "ffi", repr(C))]
 pub struct LevelInfo {
     xp: u64,
     level: u64,
     percentage: u8,
+    xp_into_level: u64,
+    xp_remaining: u64,
 }
 
 impl LevelInfo {
-    /// Create a new `LevelInfo` struct. This operation calculates the current percentage and level
-    /// immediately, rather then when the getter is called.
+    /// Create a new `LevelInfo` struct using the mee6 growth rate. This operation calculates the
+    /// current percentage and level immediately, rather then when the getter is called.
     #[must_use]
     pub fn new(xp: u64) -> Self {
-        // The operation used to calculate how many XP a given level is is (5 / 6) * level * (2 * level * level + 27 * level + 91), but it's optimized here.
-        let level = {
-            let xp = xp as f64;
-            let mut testxp = 0.0;
-            let mut level = 0;
-            while xp >= testxp {
-                level += 1;
-                testxp = Self::xp_to_level(f64::from(level));
-            }
-            level - 1
-        };
-        let last_level_xp_requirement = Self::xp_to_level(f64::from(level));
-        let next_level_xp_requirement = Self::xp_to_level(f64::from(level + 1));
+        Self::with_curve(xp, &Mee6GrowthRate)
+    }
+    /// Create a new `LevelInfo` struct against an arbitrary [`GrowthRate`] curve, calculating the
+    /// current percentage and level immediately.
+    #[must_use]
+    pub fn with_curve<G: GrowthRate>(xp: u64, curve: &G) -> Self {
+        let level = curve.level_for_xp(xp);
+        let last_level_xp_requirement = curve.xp_for_level(level);
+        let next_level_xp_requirement = curve.xp_for_level(level + 1);
         Self {
             xp,
-            level: level as u64,
-            percentage: (((xp as f64 - last_level_xp_requirement)
-                / (next_level_xp_requirement - last_level_xp_requirement))
+            level,
+            percentage: (((xp as f64 - last_level_xp_requirement as f64)
+                / (next_level_xp_requirement as f64 - last_level_xp_requirement as f64))
                 * 100.0) as u8,
+            xp_into_level: xp - last_level_xp_requirement,
+            xp_remaining: next_level_xp_requirement - xp,
         }
     }
     /// Get the xp that was input into this `LevelInfo`.
@@ -63,11 +150,22 @@ impl LevelInfo {
     pub const fn percentage(&self) -> u8 {
         self.percentage
     }
-    // mul_add is not no-std
-    #[allow(clippy::suboptimal_flops)]
+    /// Get the xp required to reach `level` under the mee6 growth rate.
+    #[must_use]
+    pub fn xp_for_level(level: u64) -> u64 {
+        Mee6GrowthRate.xp_for_level(level)
+    }
+    /// Get the xp consumed past this `LevelInfo`'s last level boundary.
+    #[must_use]
     #[inline]
-    fn xp_to_level(level: f64) -> f64 {
-        (5.0 / 6.0) * level * (2.0 * level * level + 27.0 * level + 91.0)
+    pub const fn xp_into_level(&self) -> u64 {
+        self.xp_into_level
+    }
+    /// Get the xp still needed to reach the next level.
+    #[must_use]
+    #[inline]
+    pub const fn xp_remaining(&self) -> u64 {
+        self.xp_remaining
     }
 }
 
@@ -90,6 +188,48 @@ mod tests {
         let inf = LevelInfo::new(3255);
         assert_eq!(inf.percentage(), 43);
     }
+    #[test]
+    fn xp_for_level() {
+        assert_eq!(LevelInfo::xp_for_level(8), 2900);
+    }
+    #[test]
+    fn xp_into_level() {
+        let inf = LevelInfo::new(3255);
+        assert_eq!(inf.xp_into_level(), 355);
+    }
+    #[test]
+    fn xp_remaining() {
+        let inf = LevelInfo::new(3255);
+        assert_eq!(inf.xp_remaining(), 465);
+    }
+    #[test]
+    fn xp_into_level_and_remaining_use_the_constructing_curve() {
+        let curve = LookupGrowthRate::new(&[0, 10, 50, 100]);
+        let inf = LevelInfo::with_curve(20, &curve);
+        assert_eq!(inf.xp_into_level(), 10);
+        assert_eq!(inf.xp_remaining(), 30);
+    }
+    #[test]
+    fn lookup_growth_rate_level_for_xp() {
+        let curve = LookupGrowthRate::new(&[0, 10, 50, 100]);
+        assert_eq!(curve.level_for_xp(0), 0);
+        assert_eq!(curve.level_for_xp(9), 0);
+        assert_eq!(curve.level_for_xp(10), 1);
+        assert_eq!(curve.level_for_xp(99), 2);
+        assert_eq!(curve.level_for_xp(100), 3);
+        assert_eq!(curve.level_for_xp(1000), 3);
+    }
+    #[test]
+    fn lookup_growth_rate_level_for_xp_below_first_threshold() {
+        let curve = LookupGrowthRate::new(&[10, 50, 100]);
+        assert_eq!(curve.level_for_xp(5), 0);
+    }
+    #[test]
+    fn lookup_growth_rate_xp_for_level() {
+        let curve = LookupGrowthRate::new(&[0, 10, 50, 100]);
+        assert_eq!(curve.xp_for_level(2), 50);
+        assert_eq!(curve.xp_for_level(10), 100);
+    }
 
     #[bench]
     fn create_levelinfo(b: &mut test::Bencher) {